@@ -0,0 +1,232 @@
+//! Minimal parser for CUE sheets, used by `SplitMode::Cue` to recover track
+//! boundaries and metadata from an album rip.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// One track parsed out of a `.cue` sheet
+#[derive(Debug, Clone)]
+pub struct CueTrack {
+    /// Track number as it appears after `TRACK`
+    pub number: u32,
+    /// `TITLE` tag for this track, if present
+    pub title: Option<String>,
+    /// `PERFORMER` tag for this track, falling back to the album-level `PERFORMER`
+    pub performer: Option<String>,
+    /// Start time of the track, taken from its `INDEX 01` line
+    pub start_time: Duration,
+}
+
+/// Parse a CUE sheet into its list of tracks, in file order
+///
+/// Only `TRACK`/`TITLE`/`PERFORMER`/`INDEX 01` lines are understood; `INDEX 00`
+/// pregap markers, `FILE`, and `REM` lines are ignored.
+pub fn parse_cue_sheet(path: &Path) -> io::Result<Vec<CueTrack>> {
+    let contents = fs::read_to_string(path)?;
+
+    let mut tracks = Vec::new();
+    let mut album_performer: Option<String> = None;
+
+    let mut current_number: Option<u32> = None;
+    let mut current_title: Option<String> = None;
+    let mut current_performer: Option<String> = None;
+    let mut current_start: Option<Duration> = None;
+
+    for raw_line in contents.lines() {
+        let line = raw_line.trim();
+        let mut words = line.splitn(2, char::is_whitespace);
+        let keyword = words.next().unwrap_or("").to_ascii_uppercase();
+        let rest = words.next().unwrap_or("").trim();
+
+        match keyword.as_str() {
+            "TRACK" => {
+                if let Some(number) = current_number.take() {
+                    tracks.push(finish_track(
+                        number,
+                        current_title.take(),
+                        current_performer.take(),
+                        current_start.take(),
+                        &album_performer,
+                    )?);
+                }
+                current_number = rest.split_whitespace().next().and_then(|n| n.parse().ok());
+            }
+            "TITLE" => {
+                let title = unquote(rest);
+                if current_number.is_some() {
+                    current_title = Some(title);
+                }
+            }
+            "PERFORMER" => {
+                let performer = unquote(rest);
+                if current_number.is_some() {
+                    current_performer = Some(performer);
+                } else {
+                    album_performer = Some(performer);
+                }
+            }
+            "INDEX" => {
+                let mut parts = rest.split_whitespace();
+                let index_number = parts.next().and_then(|n| n.parse::<u32>().ok());
+                let timestamp = parts.next();
+                if index_number == Some(1) {
+                    if let Some(timestamp) = timestamp {
+                        current_start = Some(parse_cue_timestamp(timestamp)?);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(number) = current_number.take() {
+        tracks.push(finish_track(
+            number,
+            current_title.take(),
+            current_performer.take(),
+            current_start.take(),
+            &album_performer,
+        )?);
+    }
+
+    Ok(tracks)
+}
+
+fn finish_track(
+    number: u32,
+    title: Option<String>,
+    performer: Option<String>,
+    start_time: Option<Duration>,
+    album_performer: &Option<String>,
+) -> io::Result<CueTrack> {
+    let start_time = start_time.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("track {} has no INDEX 01", number),
+        )
+    })?;
+    Ok(CueTrack {
+        number,
+        title,
+        performer: performer.or_else(|| album_performer.clone()),
+        start_time,
+    })
+}
+
+/// Parse a CUE `MM:SS:FF` timestamp (75 frames per second) into a `Duration`
+fn parse_cue_timestamp(timestamp: &str) -> io::Result<Duration> {
+    let fields: Vec<&str> = timestamp.split(':').collect();
+    if fields.len() != 3 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid CUE timestamp: {}", timestamp),
+        ));
+    }
+
+    let parse_field = |s: &str| {
+        s.parse::<u64>().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid CUE timestamp: {}", timestamp),
+            )
+        })
+    };
+    let minutes = parse_field(fields[0])?;
+    let seconds = parse_field(fields[1])?;
+    let frames = parse_field(fields[2])?;
+
+    let total_seconds = minutes as f64 * 60.0 + seconds as f64 + frames as f64 / 75.0;
+    Ok(Duration::from_secs_f64(total_seconds))
+}
+
+/// Strip the surrounding quotes CUE string fields are normally wrapped in
+fn unquote(s: &str) -> String {
+    s.trim().trim_matches('"').to_string()
+}
+
+/// Build a filesystem-safe output filename stem for a track, e.g. `"01 - Artist - Title"`
+pub fn track_output_name(track: &CueTrack) -> String {
+    let sanitize = |s: &str| {
+        s.chars()
+            .map(|c| if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_') { c } else { '_' })
+            .collect::<String>()
+            .trim()
+            .to_string()
+    };
+
+    let mut name = format!("{:02}", track.number);
+    if let Some(performer) = &track.performer {
+        name.push_str(" - ");
+        name.push_str(&sanitize(performer));
+    }
+    if let Some(title) = &track.title {
+        name.push_str(" - ");
+        name.push_str(&sanitize(title));
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_mm_ss_ff_timestamp() {
+        let duration = parse_cue_timestamp("01:30:00").unwrap();
+        assert_eq!(duration, Duration::from_secs(90));
+    }
+
+    #[test]
+    fn parses_timestamp_with_frames() {
+        let duration = parse_cue_timestamp("00:00:75").unwrap();
+        assert_eq!(duration, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn rejects_malformed_timestamp() {
+        assert!(parse_cue_timestamp("01:30").is_err());
+        assert!(parse_cue_timestamp("aa:bb:cc").is_err());
+    }
+
+    #[test]
+    fn parses_tracks_with_titles_and_performers() {
+        let path = std::env::temp_dir().join(format!("wav_splitter_test_{}_tracks.cue", std::process::id()));
+        fs::write(
+            &path,
+            concat!(
+                "PERFORMER \"Album Artist\"\n",
+                "TRACK 01 AUDIO\n",
+                "  TITLE \"First Song\"\n",
+                "  INDEX 01 00:00:00\n",
+                "TRACK 02 AUDIO\n",
+                "  TITLE \"Second Song\"\n",
+                "  PERFORMER \"Guest Artist\"\n",
+                "  INDEX 01 03:30:00\n",
+            ),
+        )
+        .unwrap();
+
+        let tracks = parse_cue_sheet(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks[0].title.as_deref(), Some("First Song"));
+        assert_eq!(tracks[0].performer.as_deref(), Some("Album Artist")); // falls back to album performer
+        assert_eq!(tracks[0].start_time, Duration::from_secs(0));
+        assert_eq!(tracks[1].performer.as_deref(), Some("Guest Artist"));
+        assert_eq!(tracks[1].start_time, Duration::from_secs(210));
+    }
+
+    #[test]
+    fn track_without_index_01_is_an_error() {
+        let path = std::env::temp_dir().join(format!("wav_splitter_test_{}_noindex.cue", std::process::id()));
+        fs::write(&path, "TRACK 01 AUDIO\n  TITLE \"No Index\"\n").unwrap();
+
+        let result = parse_cue_sheet(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+    }
+}
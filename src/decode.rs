@@ -0,0 +1,185 @@
+//! Decode-and-re-encode path that lets non-WAV inputs (FLAC/MP3/M4A/OGG/...)
+//! be split into genuine PCM WAV chunks instead of producing garbage from a
+//! zero-copy byte stream that was never PCM to begin with.
+//!
+//! `sample_rate`/`channels` are derived from the decoded format, and so is the
+//! re-encoded bit depth: `native_decoded_format` reads the container's
+//! reported `bits_per_sample` (e.g. 24 for a 24-bit FLAC) so lossless sources
+//! keep their precision instead of being narrowed to 16-bit PCM; codecs that
+//! don't report a depth (most lossy formats) fall back to 16-bit.
+
+use std::io;
+
+use symphonia::core::audio::{AudioBufferRef, Signal};
+use symphonia::core::codecs::{CodecParameters, CodecType, Decoder, DecoderOptions};
+use symphonia::core::conv::IntoSample;
+use symphonia::core::formats::Packet;
+
+use crate::resample::SampleFormat;
+
+/// Whether `codec` is a type Symphonia already demuxes as raw PCM, i.e. one
+/// that can use the fast zero-copy write path instead of decode/re-encode
+pub fn is_raw_pcm(codec: CodecType) -> bool {
+    use symphonia::core::codecs::*;
+    matches!(
+        codec,
+        CODEC_TYPE_PCM_S8
+            | CODEC_TYPE_PCM_U8
+            | CODEC_TYPE_PCM_S16LE
+            | CODEC_TYPE_PCM_S16BE
+            | CODEC_TYPE_PCM_S24LE
+            | CODEC_TYPE_PCM_S24BE
+            | CODEC_TYPE_PCM_S32LE
+            | CODEC_TYPE_PCM_S32BE
+            | CODEC_TYPE_PCM_F32LE
+            | CODEC_TYPE_PCM_F32BE
+            | CODEC_TYPE_PCM_F64LE
+            | CODEC_TYPE_PCM_F64BE
+    )
+}
+
+/// Whether `codec` stores its raw samples as IEEE-754 float rather than
+/// fixed-point integer PCM (`WAVE_FORMAT_IEEEFLOAT`). Only meaningful for
+/// codecs where `is_raw_pcm` is also true; needed so a passthrough float WAV
+/// isn't reinterpreted as integer PCM if it's later resampled.
+pub fn is_float_pcm(codec: CodecType) -> bool {
+    use symphonia::core::codecs::*;
+    matches!(codec, CODEC_TYPE_PCM_F32LE | CODEC_TYPE_PCM_F32BE | CODEC_TYPE_PCM_F64LE | CODEC_TYPE_PCM_F64BE)
+}
+
+/// Build a decoder for the given codec parameters
+pub fn make_decoder(codec_params: &CodecParameters) -> io::Result<Box<dyn Decoder>> {
+    symphonia::default::get_codecs()
+        .make(codec_params, &DecoderOptions::default())
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Error creating decoder: {}", e)))
+}
+
+/// Choose the PCM bit depth/encoding to re-encode a decoded track as. Prefers
+/// the container's own `bits_per_sample` (rounded up to the next width this
+/// module can write, capped at 32) so a lossless source keeps its precision; codecs that
+/// don't report a depth fall back to 16-bit integer PCM.
+pub fn native_decoded_format(codec_params: &CodecParameters) -> (u16, SampleFormat) {
+    let bits_per_sample = match codec_params.bits_per_sample {
+        Some(bits) if bits <= 8 => 8,
+        Some(bits) if bits <= 16 => 16,
+        Some(bits) if bits <= 24 => 24,
+        Some(bits) if bits > 24 => 32,
+        _ => 16,
+    };
+    // Raw float PCM is always demuxed as-is (see `is_raw_pcm`), never routed
+    // through this decode path, so a decoded track is always re-encoded as
+    // integer PCM.
+    (bits_per_sample, SampleFormat::Int)
+}
+
+/// Decode one packet and return its samples as interleaved little-endian PCM
+/// at `bits_per_sample`/`sample_format`
+pub fn decode_packet(
+    decoder: &mut dyn Decoder,
+    packet: &Packet,
+    bits_per_sample: u16,
+    sample_format: SampleFormat,
+) -> io::Result<Vec<u8>> {
+    let decoded = decoder
+        .decode(packet)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Error decoding packet: {}", e)))?;
+    Ok(interleave_pcm(&decoded, bits_per_sample, sample_format))
+}
+
+/// Convert whichever sample format Symphonia decoded into interleaved PCM
+/// bytes at `bits_per_sample`/`sample_format`, by normalizing every sample to
+/// `[-1.0, 1.0]` first so the target width is a single switch rather than one
+/// conversion per source type
+fn interleave_pcm(decoded: &AudioBufferRef, bits_per_sample: u16, sample_format: SampleFormat) -> Vec<u8> {
+    macro_rules! interleave {
+        ($buf:expr) => {{
+            let channels = $buf.spec().channels.count();
+            let frames = $buf.frames();
+            let bytes_per_sample = (bits_per_sample / 8).max(1) as usize;
+            let mut bytes = Vec::with_capacity(frames * channels * bytes_per_sample);
+            for frame in 0..frames {
+                for ch in 0..channels {
+                    let normalized: f64 = IntoSample::<f64>::into_sample($buf.chan(ch)[frame]);
+                    encode_sample(&mut bytes, normalized, bits_per_sample, sample_format);
+                }
+            }
+            bytes
+        }};
+    }
+
+    match decoded {
+        AudioBufferRef::U8(buf) => interleave!(buf),
+        AudioBufferRef::U16(buf) => interleave!(buf),
+        AudioBufferRef::U24(buf) => interleave!(buf),
+        AudioBufferRef::U32(buf) => interleave!(buf),
+        AudioBufferRef::S8(buf) => interleave!(buf),
+        AudioBufferRef::S16(buf) => interleave!(buf),
+        AudioBufferRef::S24(buf) => interleave!(buf),
+        AudioBufferRef::S32(buf) => interleave!(buf),
+        AudioBufferRef::F32(buf) => interleave!(buf),
+        AudioBufferRef::F64(buf) => interleave!(buf),
+    }
+}
+
+/// Encode one normalized `[-1.0, 1.0]` sample as little-endian PCM bytes at
+/// `bits_per_sample`/`sample_format`, appending to `bytes`. Mirrors
+/// `resample::decode_sample_i16`'s width/format match, just running in the
+/// opposite direction.
+fn encode_sample(bytes: &mut Vec<u8>, normalized: f64, bits_per_sample: u16, sample_format: SampleFormat) {
+    let normalized = normalized.clamp(-1.0, 1.0);
+    match (bits_per_sample, sample_format) {
+        (8, SampleFormat::Int) => {
+            // 8-bit WAV PCM is unsigned, centered at 128
+            bytes.push(((normalized * 127.0).round() as i16 + 128) as u8);
+        }
+        (24, SampleFormat::Int) => {
+            let sample = (normalized * 8_388_607.0).round() as i32; // 2^23 - 1
+            bytes.extend_from_slice(&sample.to_le_bytes()[..3]);
+        }
+        (32, SampleFormat::Int) => {
+            let sample = (normalized * i32::MAX as f64).round() as i32;
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+        (32, SampleFormat::Float) => bytes.extend_from_slice(&(normalized as f32).to_le_bytes()),
+        (64, SampleFormat::Float) => bytes.extend_from_slice(&normalized.to_le_bytes()),
+        // 16-bit int, and any other combination this module doesn't target
+        _ => {
+            let sample = (normalized * i16::MAX as f64).round() as i16;
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn native_decoded_format_preserves_24_bit_depth() {
+        let mut codec_params = CodecParameters::new();
+        codec_params.bits_per_sample = Some(24);
+        assert_eq!(native_decoded_format(&codec_params), (24, SampleFormat::Int));
+    }
+
+    #[test]
+    fn native_decoded_format_falls_back_to_16_bit_when_unreported() {
+        let codec_params = CodecParameters::new();
+        assert_eq!(native_decoded_format(&codec_params), (16, SampleFormat::Int));
+    }
+
+    #[test]
+    fn encode_sample_round_trips_through_24_bit_pcm() {
+        let mut bytes = Vec::new();
+        encode_sample(&mut bytes, 0.5, 24, SampleFormat::Int);
+        assert_eq!(bytes.len(), 3);
+        let raw = i32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]);
+        assert_eq!(raw, (0.5 * 8_388_607.0).round() as i32);
+    }
+
+    #[test]
+    fn encode_sample_clamps_out_of_range_input() {
+        let mut bytes = Vec::new();
+        encode_sample(&mut bytes, 2.0, 16, SampleFormat::Int);
+        assert_eq!(i16::from_le_bytes([bytes[0], bytes[1]]), i16::MAX);
+    }
+}
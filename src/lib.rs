@@ -1,13 +1,27 @@
+mod cue;
+mod decode;
+mod resample;
+mod riff;
+mod silence;
+
+pub use resample::InterpolationMode;
+
 use std::fs::{self, File};
-use std::io::{self, Write, BufWriter, Read, Seek, SeekFrom};
+use std::io::{self, Write, BufWriter};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use symphonia::core::io::{MediaSourceStream, ReadOnlySource};
-use symphonia::core::formats::FormatOptions;
+use symphonia::core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo};
 use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
 use symphonia::core::codecs::CodecParameters;
+use symphonia::core::units::Time;
+
+/// Default RMS level (dBFS) below which a window counts as silent, used by `SplitMode::Silence`
+pub const DEFAULT_SILENCE_THRESHOLD_DB: f64 = -40.0;
+/// Default minimum consecutive silence needed to qualify as a cut point, used by `SplitMode::Silence`
+pub const DEFAULT_MIN_SILENCE_MS: u64 = 500;
 
 /// Information about an audio chunk
 pub struct ChunkInfo {
@@ -15,19 +29,68 @@ pub struct ChunkInfo {
     pub start_time: Duration,
     /// End time of the chunk
     pub end_time: Duration,
+    /// Output filename stem to use instead of the numeric `prefix_NNN` scheme
+    name: Option<String>,
     packets: Vec<usize>, // Indices of packets in the global packets list
 }
 
+/// A planned chunk boundary in time, optionally carrying an explicit output name
+struct ChunkBoundary {
+    start_time: Duration,
+    end_time: Duration,
+    name: Option<String>,
+}
+
+/// How to write each output file's WAV header, decided once per input by its codec
+enum OutputFormat {
+    /// Source is already PCM WAV: echo its RIFF chunks verbatim
+    PassthroughWav(riff::WavChunks),
+    /// Source needed decoding: synthesize a PCM header with the decoded
+    /// `sample_rate`/`channels`, re-encoded at `bits_per_sample`/`sample_format`
+    /// as chosen by `decode::native_decoded_format`
+    DecodedPcm { sample_rate: u32, channels: u16, bits_per_sample: u16, sample_format: resample::SampleFormat },
+}
+
+/// How an input file should be cut into chunks
+pub enum SplitMode {
+    /// Split into fixed-duration chunks
+    Duration(Duration),
+    /// Split at the track boundaries described by an accompanying CUE sheet
+    Cue(PathBuf),
+    /// Split near fixed-duration marks, snapped to the nearest detected silence
+    Silence(Duration),
+}
+
 /// Configuration options for WAV splitting
 pub struct SplitOptions<'a> {
     /// Path to the input WAV file
     pub input_path: &'a Path,
-    /// Desired duration for each chunk
-    pub chunk_duration: Duration,
+    /// How to determine chunk boundaries
+    pub split_mode: SplitMode,
     /// Directory where output files will be saved
     pub output_dir: &'a Path,
     /// Prefix for output filenames
     pub prefix: &'a str,
+    /// RMS level (dBFS) below which a window is considered silent, used by `SplitMode::Silence`
+    pub silence_threshold_db: f64,
+    /// Minimum consecutive silence needed to qualify as a cut point, used by `SplitMode::Silence`
+    pub min_silence_ms: u64,
+    /// Resample each chunk to this rate if set (e.g. 16 kHz for speech pipelines)
+    pub target_sample_rate: Option<u32>,
+    /// Interpolation kernel used when `target_sample_rate` is set
+    pub interpolation_mode: InterpolationMode,
+}
+
+/// Which implementation actually produced a `SplitResult`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitStrategy {
+    /// Boundaries were computed from the track's known length up front, and
+    /// each chunk was read by seeking directly to it; at most one chunk's
+    /// worth of packets was ever held in memory at a time
+    Streaming,
+    /// The source's length or seek support wasn't available, so every packet
+    /// was buffered up front and chunks were sliced out of memory afterwards
+    Buffered,
 }
 
 /// Result of WAV splitting operation
@@ -38,6 +101,8 @@ pub struct SplitResult {
     pub total_duration: Duration,
     /// Paths to generated output files
     pub output_files: Vec<PathBuf>,
+    /// Which implementation was used to produce this result
+    pub strategy: SplitStrategy,
 }
 
 /// Split a WAV file into chunks of specified duration
@@ -50,15 +115,19 @@ pub struct SplitResult {
 ///
 /// # Example
 /// ```no_run
-/// use wav_splitter::{SplitOptions, split_wav};
+/// use wav_splitter::{SplitOptions, SplitMode, split_wav, DEFAULT_SILENCE_THRESHOLD_DB, DEFAULT_MIN_SILENCE_MS};
 /// use std::path::Path;
 /// use std::time::Duration;
 ///
 /// let options = SplitOptions {
 ///     input_path: Path::new("input.wav"),
-///     chunk_duration: Duration::from_secs(600), // 10 minutes
+///     split_mode: SplitMode::Duration(Duration::from_secs(600)), // 10 minutes
 ///     output_dir: Path::new("chunks"),
 ///     prefix: "track",
+///     silence_threshold_db: DEFAULT_SILENCE_THRESHOLD_DB,
+///     min_silence_ms: DEFAULT_MIN_SILENCE_MS,
+///     target_sample_rate: None,
+///     interpolation_mode: Default::default(),
 /// };
 ///
 /// match split_wav(&options) {
@@ -68,34 +137,239 @@ pub struct SplitResult {
 /// ```
 pub fn split_wav(options: &SplitOptions) -> io::Result<SplitResult> {
     println!("Processing file: {}", options.input_path.display());
-    println!("Target chunk duration: {} seconds ({} minutes)", 
-        options.chunk_duration.as_secs(), 
-        options.chunk_duration.as_secs() / 60);
-    
+    match &options.split_mode {
+        SplitMode::Duration(chunk_duration) => println!(
+            "Target chunk duration: {} seconds ({} minutes)",
+            chunk_duration.as_secs(),
+            chunk_duration.as_secs() / 60
+        ),
+        SplitMode::Cue(cue_path) => println!("Splitting at track boundaries from {}", cue_path.display()),
+        SplitMode::Silence(nominal_duration) => println!(
+            "Splitting near {}-second marks, snapped to detected silence",
+            nominal_duration.as_secs()
+        ),
+    }
+
     // Create output directory if it doesn't exist
     if !options.output_dir.exists() {
         fs::create_dir_all(options.output_dir)?;
     }
-    
+
+    // Prefer the single-pass streaming path: it only works when the source
+    // reports its own length and supports seeking, so fall back to the
+    // buffered two-pass implementation whenever either is missing.
+    match try_split_streaming(options)? {
+        Some(result) => {
+            println!(
+                "Successfully split (single-pass streaming) into {} chunks in directory: {}",
+                result.chunk_count,
+                options.output_dir.display()
+            );
+            Ok(result)
+        }
+        None => {
+            println!("Seeking unsupported (or track length unknown); falling back to buffered two-pass splitting");
+            split_wav_buffered(options)
+        }
+    }
+}
+
+/// How far a seek's `actual_ts` may land from the requested `required_ts`
+/// before the boundary is considered unreliable and streaming is abandoned
+const SEEK_TOLERANCE: Duration = Duration::from_millis(200);
+
+/// Attempt to split `options.input_path` in a single streaming pass: compute
+/// every chunk boundary up front from the track's known length, then `seek`
+/// straight to each one and stream its packets to the output file, never
+/// holding more than the current chunk in memory.
+///
+/// Returns `Ok(None)` when the track doesn't report a `time_base`/`n_frames`
+/// (so the total duration can't be known without a first pass), when
+/// `format.seek` fails, e.g. because the demuxer doesn't support seeking for
+/// this container, or when a seek lands further than `SEEK_TOLERANCE` from
+/// the requested position. Each case means the caller should fall back to
+/// `split_wav_buffered` instead.
+fn try_split_streaming(options: &SplitOptions) -> io::Result<Option<SplitResult>> {
+    let mut format = open_format_reader(options.input_path)?;
+
+    let track = format
+        .default_track()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No default track found"))?
+        .clone();
+    let track_id = track.id;
+    let codec_params = track.codec_params.clone();
+
+    let time_base = match codec_params.time_base {
+        Some(time_base) => time_base,
+        None => return Ok(None),
+    };
+    let n_frames = match codec_params.n_frames {
+        Some(n_frames) => n_frames,
+        None => return Ok(None),
+    };
+    let total_duration =
+        Duration::from_secs_f64(n_frames as f64 * time_base.numer as f64 / time_base.denom as f64);
+
+    let boundaries = match &options.split_mode {
+        SplitMode::Duration(chunk_duration) => duration_boundaries(total_duration, *chunk_duration)?,
+        SplitMode::Cue(cue_path) => cue_boundaries(cue_path, total_duration)?,
+        SplitMode::Silence(nominal_duration) => silence_boundaries(
+            options.input_path,
+            total_duration,
+            *nominal_duration,
+            options.silence_threshold_db,
+            options.min_silence_ms,
+        )?,
+    };
+
+    let passthrough = decode::is_raw_pcm(codec_params.codec);
+    let mut decoder = if passthrough { None } else { Some(decode::make_decoder(&codec_params)?) };
+
+    let output_format = if passthrough {
+        OutputFormat::PassthroughWav(riff::parse_riff_chunks(options.input_path)?)
+    } else {
+        let (bits_per_sample, sample_format) = decode::native_decoded_format(&codec_params);
+        OutputFormat::DecodedPcm {
+            sample_rate: codec_params.sample_rate.unwrap_or(44100),
+            channels: codec_params.channels.map(|c| c.count() as u16).unwrap_or(2),
+            bits_per_sample,
+            sample_format,
+        }
+    };
+
+    let (source_sample_rate, source_channels, source_bits_per_sample, source_sample_format) = match &output_format {
+        OutputFormat::PassthroughWav(_) => (
+            codec_params.sample_rate.unwrap_or(44100),
+            codec_params.channels.map(|c| c.count() as u16).unwrap_or(2),
+            codec_params.bits_per_sample.unwrap_or(16) as u16,
+            if decode::is_float_pcm(codec_params.codec) { resample::SampleFormat::Float } else { resample::SampleFormat::Int },
+        ),
+        OutputFormat::DecodedPcm { sample_rate, channels, bits_per_sample, sample_format } => {
+            (*sample_rate, *channels, *bits_per_sample, *sample_format)
+        }
+    };
+
+    println!("Streaming pass: seeking to {} chunk boundaries...", boundaries.len());
+
+    let mut output_files = Vec::with_capacity(boundaries.len());
+
+    for (chunk_idx, boundary) in boundaries.iter().enumerate() {
+        let seek_time = Time {
+            seconds: boundary.start_time.as_secs(),
+            frac: boundary.start_time.subsec_nanos() as f64 / 1_000_000_000.0,
+        };
+
+        let seeked = match format.seek(SeekMode::Accurate, SeekTo::Time { time: seek_time, track_id: Some(track_id) }) {
+            Ok(seeked) => seeked,
+            Err(_) => {
+                // This source doesn't support (accurate) seeking; abandon the
+                // streaming attempt entirely rather than mixing strategies.
+                cleanup_streaming_attempt(&output_files);
+                return Ok(None);
+            }
+        };
+
+        // Some containers/codecs can only seek to a coarser packet boundary
+        // than requested (normal for compressed formats). If the demuxer
+        // landed too far from where we asked, this chunk's boundary would
+        // silently overlap or gap with its neighbour, so bail out to the
+        // buffered path instead of producing misaligned audio.
+        let actual_time = Duration::from_secs_f64(
+            seeked.actual_ts as f64 * time_base.numer as f64 / time_base.denom as f64,
+        );
+        let required_time = Duration::from_secs_f64(
+            seeked.required_ts as f64 * time_base.numer as f64 / time_base.denom as f64,
+        );
+        if duration_abs_diff(actual_time, required_time) > SEEK_TOLERANCE {
+            cleanup_streaming_attempt(&output_files);
+            return Ok(None);
+        }
+
+        if let Some(decoder) = decoder.as_deref_mut() {
+            decoder.reset();
+        }
+
+        // Read forward from the seek point until this chunk's share of the
+        // track has been consumed; only ever one chunk's packets are held.
+        let target_len = boundary.end_time.saturating_sub(boundary.start_time);
+        let is_last_chunk = chunk_idx + 1 == boundaries.len();
+        let mut chunk_bytes = Vec::new();
+        let mut elapsed = Duration::from_secs(0);
+
+        while is_last_chunk || elapsed < target_len {
+            let packet = match format.next_packet() {
+                Ok(packet) => packet,
+                Err(_) => break, // end of stream
+            };
+            if packet.track_id() != track_id {
+                continue;
+            }
+
+            let packet_duration = Duration::from_secs_f64(
+                packet.dur as f64 * time_base.numer as f64 / time_base.denom as f64,
+            );
+            let packet_bytes = match decoder.as_deref_mut() {
+                Some(decoder) => decode::decode_packet(decoder, &packet, source_bits_per_sample, source_sample_format)?,
+                None => packet.data.to_vec(),
+            };
+            chunk_bytes.extend_from_slice(&packet_bytes);
+            elapsed += packet_duration;
+        }
+
+        let output_filename = match &boundary.name {
+            Some(name) => format!("{}.wav", name),
+            None => format!("{}_{:03}.wav", options.prefix, chunk_idx + 1),
+        };
+        let output_path = options.output_dir.join(&output_filename);
+
+        println!(
+            "Writing chunk {}/{}: {} (duration: {:.2} minutes)",
+            chunk_idx + 1,
+            boundaries.len(),
+            output_filename,
+            (boundary.end_time - boundary.start_time).as_secs_f64() / 60.0
+        );
+
+        write_chunk_file(
+            &output_path,
+            &chunk_bytes,
+            &output_format,
+            options.target_sample_rate,
+            options.interpolation_mode,
+            source_sample_rate,
+            source_channels,
+            source_bits_per_sample,
+            source_sample_format,
+        )?;
+        output_files.push(output_path);
+    }
+
+    Ok(Some(SplitResult {
+        chunk_count: boundaries.len(),
+        total_duration,
+        output_files,
+        strategy: SplitStrategy::Streaming,
+    }))
+}
+
+/// Delete the chunk files already written by an abandoned streaming attempt.
+/// `try_split_streaming` falls back to `split_wav_buffered` on a seek failure
+/// or tolerance miss, which may produce a different number (or set) of
+/// chunks, so the partial files from the abandoned attempt must not linger
+/// alongside the buffered pass's output.
+fn cleanup_streaming_attempt(output_files: &[PathBuf]) {
+    for path in output_files {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Buffered two-pass fallback: read every packet into memory up front, then
+/// slice chunks out of that buffer. Used when `try_split_streaming` can't
+/// determine the track's length or its seek attempt fails.
+fn split_wav_buffered(options: &SplitOptions) -> io::Result<SplitResult> {
     // Open the media source
-    let file = Box::new(ReadOnlySource::new(File::open(options.input_path)?));
-    let mss = MediaSourceStream::new(file, Default::default());
-    
-    // Create a hint to help with format detection
-    let mut hint = Hint::new();
-    hint.with_extension("wav");
-    
-    // Use default options
-    let format_opts = FormatOptions::default();
-    let metadata_opts = MetadataOptions::default();
-    
-    // Probe the format
-    let probed = symphonia::default::get_probe()
-        .format(&hint, mss, &format_opts, &metadata_opts)
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Error probing format: {}", e)))?;
-    
-    let mut format = probed.format;
-    
+    let mut format = open_format_reader(options.input_path)?;
+
     // Get the default track
     let track = format.default_track()
         .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No default track found"))?;
@@ -105,11 +379,16 @@ pub fn split_wav(options: &SplitOptions) -> io::Result<SplitResult> {
     let time_base = codec_params.time_base
         .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No time base found"))?;
     
-    // Store all packets and their durations
-    let mut packets = Vec::new();
+    // Already-PCM WAV input can be copied byte-for-byte; anything else needs decoding
+    let passthrough = decode::is_raw_pcm(codec_params.codec);
+    let mut decoder = if passthrough { None } else { Some(decode::make_decoder(&codec_params)?) };
+    let (decoded_bits_per_sample, decoded_sample_format) = decode::native_decoded_format(&codec_params);
+
+    // Store each packet's output bytes (raw or decoded) and their durations
+    let mut packets: Vec<Vec<u8>> = Vec::new();
     let mut packet_times = Vec::new();
     let mut total_duration = Duration::from_secs(0);
-    
+
     // First pass: read all packets and calculate timestamps
     println!("First pass: reading packets and calculating timestamps...");
     while let Ok(packet) = format.next_packet() {
@@ -118,10 +397,15 @@ pub fn split_wav(options: &SplitOptions) -> io::Result<SplitResult> {
         let packet_duration = Duration::from_secs_f64(
             frame_len as f64 * time_base.numer as f64 / time_base.denom as f64
         );
-        
+
         total_duration += packet_duration;
         packet_times.push(total_duration);
-        packets.push(packet);
+
+        let packet_bytes = match decoder.as_deref_mut() {
+            Some(decoder) => decode::decode_packet(decoder, &packet, decoded_bits_per_sample, decoded_sample_format)?,
+            None => packet.data.to_vec(),
+        };
+        packets.push(packet_bytes);
     }
     
     if packets.is_empty() {
@@ -136,56 +420,57 @@ pub fn split_wav(options: &SplitOptions) -> io::Result<SplitResult> {
     
     // Second pass: determine chunk boundaries
     println!("Second pass: determining chunk boundaries...");
-    let mut chunks = Vec::new();
-    let mut chunk_start_packet = 0;
-    let mut chunk_start_time = Duration::from_secs(0);
-    
-    while chunk_start_packet < packets.len() {
-        // Find the packet that would end this chunk
-        let target_end_time = chunk_start_time + options.chunk_duration;
-        
+    let boundaries = match &options.split_mode {
+        SplitMode::Duration(chunk_duration) => duration_boundaries(total_duration, *chunk_duration)?,
+        SplitMode::Cue(cue_path) => cue_boundaries(cue_path, total_duration)?,
+        SplitMode::Silence(nominal_duration) => silence_boundaries(
+            options.input_path,
+            total_duration,
+            *nominal_duration,
+            options.silence_threshold_db,
+            options.min_silence_ms,
+        )?,
+    };
+
+    // Map each time boundary onto the packets that fall within it
+    let mut chunks = Vec::with_capacity(boundaries.len());
+    let mut next_packet = 0;
+
+    for boundary in boundaries {
+        let chunk_start_packet = next_packet;
+
         // Find the packet index that's closest to our target end time
         let mut chunk_end_packet = chunk_start_packet;
-        while chunk_end_packet < packets.len() && 
-              (chunk_end_packet == chunk_start_packet || 
-               packet_times[chunk_end_packet - 1] < target_end_time) {
+        while chunk_end_packet < packets.len() &&
+              (chunk_end_packet == chunk_start_packet ||
+               packet_times[chunk_end_packet - 1] < boundary.end_time) {
             chunk_end_packet += 1;
         }
-        
+
         // Ensure we include at least one packet
         if chunk_end_packet == chunk_start_packet {
             chunk_end_packet = chunk_start_packet + 1;
         }
-        
-        // Get the actual end time for this chunk
-        let chunk_end_time = if chunk_end_packet < packets.len() {
-            packet_times[chunk_end_packet - 1]
-        } else {
-            total_duration
-        };
-        
+        chunk_end_packet = chunk_end_packet.min(packets.len());
+
         // Create packet index list for this chunk
-        let mut chunk_packets = Vec::new();
-        for i in chunk_start_packet..chunk_end_packet {
-            chunk_packets.push(i);
-        }
-        
+        let chunk_packets: Vec<usize> = (chunk_start_packet..chunk_end_packet).collect();
+
         chunks.push(ChunkInfo {
-            start_time: chunk_start_time,
-            end_time: chunk_end_time,
+            start_time: boundary.start_time,
+            end_time: boundary.end_time,
+            name: boundary.name,
             packets: chunk_packets,
         });
-        
-        // Move to next chunk
-        chunk_start_packet = chunk_end_packet;
-        chunk_start_time = chunk_end_time;
-        
+
+        next_packet = chunk_end_packet;
+
         // Break if we've processed all packets
-        if chunk_start_packet >= packets.len() {
+        if next_packet >= packets.len() {
             break;
         }
     }
-    
+
     println!("Splitting into {} chunks:", chunks.len());
     
     // Debug output to check chunk durations
@@ -195,38 +480,43 @@ pub fn split_wav(options: &SplitOptions) -> io::Result<SplitResult> {
             i+1, duration/60.0, duration, chunk.packets.len());
     }
     
-    // Read the WAV header from the original file to use as a template
-    let mut original_file = File::open(options.input_path)?;
-    let mut header_buf = Vec::new();
-    
-    // Read first 44 bytes (standard WAV header)
-    let header_size = 44;
-    original_file.seek(SeekFrom::Start(0))?;
-    let bytes_read = io::Read::take(&mut original_file, header_size as u64)
-        .read_to_end(&mut header_buf)?;
-    
-    if bytes_read < header_size {
-        return Err(io::Error::new(io::ErrorKind::Other, "Failed to read WAV header"));
-    }
-    
+    // Already-PCM WAV input echoes the source's RIFF chunks verbatim; decoded
+    // input gets a freshly synthesized PCM header at its native decoded depth instead
+    let output_format = if passthrough {
+        OutputFormat::PassthroughWav(riff::parse_riff_chunks(options.input_path)?)
+    } else {
+        OutputFormat::DecodedPcm {
+            sample_rate: codec_params.sample_rate.unwrap_or(44100),
+            channels: codec_params.channels.map(|c| c.count() as u16).unwrap_or(2),
+            bits_per_sample: decoded_bits_per_sample,
+            sample_format: decoded_sample_format,
+        }
+    };
+
+    // Source PCM layout, needed if a chunk has to be resampled
+    let (source_sample_rate, source_channels, source_bits_per_sample, source_sample_format) = match &output_format {
+        OutputFormat::PassthroughWav(_) => (
+            codec_params.sample_rate.unwrap_or(44100),
+            codec_params.channels.map(|c| c.count() as u16).unwrap_or(2),
+            codec_params.bits_per_sample.unwrap_or(16) as u16,
+            if decode::is_float_pcm(codec_params.codec) { resample::SampleFormat::Float } else { resample::SampleFormat::Int },
+        ),
+        OutputFormat::DecodedPcm { sample_rate, channels, bits_per_sample, sample_format } => {
+            (*sample_rate, *channels, *bits_per_sample, *sample_format)
+        }
+    };
+
     // Store output file paths
     let mut output_files = Vec::with_capacity(chunks.len());
-    
-    // Get sample rate and other parameters to calculate correct WAV header for each chunk
-    let sample_rate = codec_params.sample_rate.unwrap_or(44100);
-    let channels = codec_params.channels.unwrap_or(symphonia::core::audio::Channels::FRONT_LEFT | symphonia::core::audio::Channels::FRONT_RIGHT).count();
-    let bits_per_sample = match codec_params.bits_per_sample {
-        Some(bits) => bits as u16,
-        None => 16, // Default to 16-bit
-    };
-    let bytes_per_sample = (bits_per_sample / 8) as u16;
-    
+
     // Third pass: write chunks to files
     for (chunk_idx, chunk) in chunks.iter().enumerate() {
-        let output_filename = format!("{}_{:03}.wav", options.prefix, chunk_idx + 1);
+        let output_filename = match &chunk.name {
+            Some(name) => format!("{}.wav", name),
+            None => format!("{}_{:03}.wav", options.prefix, chunk_idx + 1),
+        };
         let output_path = options.output_dir.join(&output_filename);
-        output_files.push(output_path.clone());
-        
+
         println!(
             "Writing chunk {}/{}: {} (duration: {:.2} minutes, {} packets)",
             chunk_idx + 1,
@@ -235,72 +525,365 @@ pub fn split_wav(options: &SplitOptions) -> io::Result<SplitResult> {
             (chunk.end_time - chunk.start_time).as_secs_f64() / 60.0,
             chunk.packets.len()
         );
-        
-        let mut output = BufWriter::new(File::create(&output_path)?);
-        
-        // Calculate chunk data size
-        let mut chunk_data_size: u32 = 0;
-        for &packet_idx in &chunk.packets {
-            chunk_data_size += packets[packet_idx].data.len() as u32;
-        }
-        
-        // Write WAV header
-        write_wav_header(&mut output, chunk_data_size, sample_rate, channels as u16, bits_per_sample, bytes_per_sample)?;
-        
-        // Write all packets for this chunk
+
+        let mut chunk_bytes = Vec::new();
         for &packet_idx in &chunk.packets {
-            output.write_all(&packets[packet_idx].data)?;
+            chunk_bytes.extend_from_slice(&packets[packet_idx]);
         }
-        output.flush()?;
+
+        write_chunk_file(
+            &output_path,
+            &chunk_bytes,
+            &output_format,
+            options.target_sample_rate,
+            options.interpolation_mode,
+            source_sample_rate,
+            source_channels,
+            source_bits_per_sample,
+            source_sample_format,
+        )?;
+        output_files.push(output_path);
     }
-    
-    println!("Successfully split WAV file into {} chunks in directory: {}", 
+
+    println!("Successfully split WAV file into {} chunks in directory: {}",
         chunks.len(), options.output_dir.display());
-    
+
     Ok(SplitResult {
         chunk_count: chunks.len(),
         total_duration,
         output_files,
+        strategy: SplitStrategy::Buffered,
     })
 }
 
-/// Write a proper WAV header to the output file
-fn write_wav_header(
+/// Write one chunk's PCM bytes out as a complete WAV file: resampling first if
+/// `target_sample_rate` is set, otherwise using `output_format`'s header
+/// (verbatim source `fmt `/`fact`/metadata, or a synthesized PCM one at the
+/// decoded `bits_per_sample`)
+fn write_chunk_file(
+    output_path: &Path,
+    chunk_bytes: &[u8],
+    output_format: &OutputFormat,
+    target_sample_rate: Option<u32>,
+    interpolation_mode: InterpolationMode,
+    source_sample_rate: u32,
+    source_channels: u16,
+    source_bits_per_sample: u16,
+    source_sample_format: resample::SampleFormat,
+) -> io::Result<()> {
+    let mut output = BufWriter::new(File::create(output_path)?);
+
+    if let Some(target_sample_rate) = target_sample_rate {
+        let resampled = resample::resample_pcm_bytes(
+            chunk_bytes,
+            source_channels,
+            source_bits_per_sample,
+            source_sample_format,
+            source_sample_rate,
+            target_sample_rate,
+            interpolation_mode,
+        );
+
+        // Resampling always re-encodes to a fresh 16-bit PCM header, but a
+        // passthrough source's preserved metadata chunks (LIST/INFO/cue ...)
+        // still need to survive the trip, same as the non-resampled path below.
+        let preserved_metadata = match output_format {
+            OutputFormat::PassthroughWav(wav_chunks) => Some(wav_chunks),
+            OutputFormat::DecodedPcm { .. } => None,
+        };
+        let trailing_bytes = preserved_metadata
+            .map(|wav_chunks| {
+                wav_chunks
+                    .metadata_chunks
+                    .iter()
+                    .map(|(_, body)| chunk_size_on_disk(body.len() as u32))
+                    .sum()
+            })
+            .unwrap_or(0);
+
+        // resample_pcm_bytes always settles on 16-bit integer PCM regardless of
+        // the source's depth, so the header matches that rather than `output_format`
+        write_pcm_wav_header(
+            &mut output,
+            resampled.len() as u32,
+            target_sample_rate,
+            source_channels,
+            16,
+            resample::SampleFormat::Int,
+            trailing_bytes,
+        )?;
+        output.write_all(&resampled)?;
+        if let Some(wav_chunks) = preserved_metadata {
+            write_metadata_chunks(&mut output, wav_chunks)?;
+        }
+    } else {
+        match output_format {
+            // Write the WAV header, echoing the original fmt/fact chunks byte-for-byte
+            OutputFormat::PassthroughWav(wav_chunks) => {
+                write_wav_header(&mut output, wav_chunks, chunk_bytes.len() as u32)?;
+                write_padded(&mut output, chunk_bytes)?;
+                // Re-append preserved metadata (LIST/INFO/cue points, ...) after the audio data
+                write_metadata_chunks(&mut output, wav_chunks)?;
+            }
+            OutputFormat::DecodedPcm { sample_rate, channels, bits_per_sample, sample_format } => {
+                write_pcm_wav_header(&mut output, chunk_bytes.len() as u32, *sample_rate, *channels, *bits_per_sample, *sample_format, 0)?;
+                write_padded(&mut output, chunk_bytes)?;
+            }
+        }
+    }
+
+    output.flush()
+}
+
+/// Probe `path` and open it as a Symphonia format reader
+fn open_format_reader(path: &Path) -> io::Result<Box<dyn FormatReader>> {
+    let file = Box::new(ReadOnlySource::new(File::open(path)?));
+    let mss = MediaSourceStream::new(file, Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|ext| ext.to_str()) {
+        hint.with_extension(extension);
+    }
+
+    let format_opts = FormatOptions::default();
+    let metadata_opts = MetadataOptions::default();
+
+    let probed = symphonia::default::get_probe()
+        .format(&hint, mss, &format_opts, &metadata_opts)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Error probing format: {}", e)))?;
+
+    Ok(probed.format)
+}
+
+/// Lay out uniform, fixed-duration chunk boundaries across the whole track
+fn duration_boundaries(total_duration: Duration, chunk_duration: Duration) -> io::Result<Vec<ChunkBoundary>> {
+    if chunk_duration.is_zero() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "chunk duration must be greater than zero"));
+    }
+
+    let mut boundaries = Vec::new();
+    let mut start = Duration::from_secs(0);
+
+    while start < total_duration {
+        let end = (start + chunk_duration).min(total_duration);
+        boundaries.push(ChunkBoundary { start_time: start, end_time: end, name: None });
+        start = end;
+    }
+
+    Ok(boundaries)
+}
+
+/// Lay out chunk boundaries from a CUE sheet's `INDEX 01` track marks, using
+/// each track's `TITLE`/`PERFORMER` to name its output file. The final track
+/// runs to `total_duration`.
+fn cue_boundaries(cue_path: &Path, total_duration: Duration) -> io::Result<Vec<ChunkBoundary>> {
+    let tracks = cue::parse_cue_sheet(cue_path)?;
+
+    if tracks.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "CUE sheet has no tracks"));
+    }
+
+    let mut boundaries = Vec::with_capacity(tracks.len());
+    for (i, track) in tracks.iter().enumerate() {
+        let end_time = tracks.get(i + 1).map(|next| next.start_time).unwrap_or(total_duration);
+        boundaries.push(ChunkBoundary {
+            start_time: track.start_time,
+            end_time,
+            name: Some(cue::track_output_name(track)),
+        });
+    }
+
+    Ok(boundaries)
+}
+
+/// Lay out chunk boundaries at `nominal_duration` marks, snapped to the
+/// nearest qualifying silence detected within a search tolerance; a mark with
+/// no silence in range keeps its exact duration-based position.
+fn silence_boundaries(
+    input_path: &Path,
+    total_duration: Duration,
+    nominal_duration: Duration,
+    threshold_db: f64,
+    min_silence_ms: u64,
+) -> io::Result<Vec<ChunkBoundary>> {
+    let nominal = duration_boundaries(total_duration, nominal_duration)?;
+    if nominal.len() <= 1 {
+        return Ok(nominal);
+    }
+
+    let silence_points = silence::find_silence_points(input_path, threshold_db, min_silence_ms)?;
+    let tolerance = nominal_duration / 4;
+
+    // Snap every internal mark to the closest qualifying silence within tolerance
+    let mut cuts = Vec::with_capacity(nominal.len() - 1);
+    for boundary in &nominal[..nominal.len() - 1] {
+        let target = boundary.end_time;
+        let snapped = silence_points
+            .iter()
+            .map(|point| point.time)
+            .filter(|&time| duration_abs_diff(time, target) <= tolerance)
+            .min_by_key(|&time| duration_abs_diff(time, target))
+            .unwrap_or(target);
+        cuts.push(snapped);
+    }
+
+    let mut boundaries = Vec::with_capacity(nominal.len());
+    let mut start = Duration::from_secs(0);
+    for cut in cuts {
+        boundaries.push(ChunkBoundary { start_time: start, end_time: cut, name: None });
+        start = cut;
+    }
+    boundaries.push(ChunkBoundary { start_time: start, end_time: total_duration, name: None });
+
+    Ok(boundaries)
+}
+
+/// Absolute difference between two `Duration`s
+fn duration_abs_diff(a: Duration, b: Duration) -> Duration {
+    if a > b { a - b } else { b - a }
+}
+
+/// Write a WAV header to the output file, echoing the source's `fmt `/`fact`
+/// chunks byte-for-byte instead of synthesizing a minimal PCM header. This is
+/// what lets `WAVE_FORMAT_EXTENSIBLE`/multichannel layouts survive a split.
+fn write_wav_header(writer: &mut impl Write, chunks: &riff::WavChunks, data_size: u32) -> io::Result<()> {
+    let mut riff_size: u32 = 4; // "WAVE"
+    riff_size += chunk_size_on_disk(chunks.fmt_chunk.len() as u32);
+    if let Some(fact) = &chunks.fact_chunk {
+        riff_size += chunk_size_on_disk(fact.len() as u32);
+    }
+    riff_size += chunk_size_on_disk(data_size);
+    for (_, body) in &chunks.metadata_chunks {
+        riff_size += chunk_size_on_disk(body.len() as u32);
+    }
+
+    // RIFF header
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&riff_size.to_le_bytes())?;
+    writer.write_all(b"WAVE")?;
+
+    // fmt chunk, verbatim from the source
+    write_chunk(writer, b"fmt ", &chunks.fmt_chunk)?;
+
+    // fact chunk, verbatim from the source, if present
+    if let Some(fact) = &chunks.fact_chunk {
+        write_chunk(writer, b"fact", fact)?;
+    }
+
+    // data chunk header; the caller streams the payload separately
+    writer.write_all(b"data")?;
+    writer.write_all(&data_size.to_le_bytes())?;
+
+    Ok(())
+}
+
+/// Write a minimal PCM WAV header for freshly decoded/re-encoded or resampled
+/// audio, with the given `sample_rate`/`channels`/`bits_per_sample` and a
+/// format tag of 1 (integer PCM) or 3 (IEEE float) to match `sample_format`
+fn write_pcm_wav_header(
     writer: &mut impl Write,
     data_size: u32,
     sample_rate: u32,
     channels: u16,
     bits_per_sample: u16,
-    bytes_per_sample: u16
+    sample_format: resample::SampleFormat,
+    trailing_bytes: u32,
 ) -> io::Result<()> {
-    // Calculate important values
-    let byte_rate = sample_rate * (channels as u32) * (bytes_per_sample as u32);
-    let block_align = channels * bytes_per_sample;
-    let file_size = data_size + 36; // 36 + data_size
-    
-    // RIFF header
+    let format_tag: u16 = match sample_format {
+        resample::SampleFormat::Int => 1,
+        resample::SampleFormat::Float => 3,
+    };
+    let bytes_per_sample = (bits_per_sample / 8).max(1) as u32;
+    let byte_rate = sample_rate * channels as u32 * bytes_per_sample;
+    let block_align = channels * bytes_per_sample as u16;
+    // 4 ("WAVE") + 24 (fmt id/size/body) + 8 (data id/size) + padded data + any chunks after it
+    let file_size = 4 + 24 + chunk_size_on_disk(data_size) + trailing_bytes;
+
     writer.write_all(b"RIFF")?;
     writer.write_all(&file_size.to_le_bytes())?;
     writer.write_all(b"WAVE")?;
-    
-    // fmt chunk
+
     writer.write_all(b"fmt ")?;
     writer.write_all(&16u32.to_le_bytes())?; // Chunk size (16 for PCM)
-    writer.write_all(&1u16.to_le_bytes())?;  // Audio format (1 = PCM)
+    writer.write_all(&format_tag.to_le_bytes())?;
     writer.write_all(&channels.to_le_bytes())?;
     writer.write_all(&sample_rate.to_le_bytes())?;
     writer.write_all(&byte_rate.to_le_bytes())?;
     writer.write_all(&block_align.to_le_bytes())?;
     writer.write_all(&bits_per_sample.to_le_bytes())?;
-    
-    // data chunk
+
     writer.write_all(b"data")?;
     writer.write_all(&data_size.to_le_bytes())?;
-    
+
+    Ok(())
+}
+
+/// Re-emit preserved `LIST`/`INFO`/`cue `/etc. chunks after the `data` chunk
+fn write_metadata_chunks(writer: &mut impl Write, chunks: &riff::WavChunks) -> io::Result<()> {
+    for (id, body) in &chunks.metadata_chunks {
+        write_chunk(writer, id, body)?;
+    }
+    Ok(())
+}
+
+/// Write one `id`+`size`+`body` RIFF chunk, padding to a 2-byte boundary
+fn write_chunk(writer: &mut impl Write, id: &[u8; 4], body: &[u8]) -> io::Result<()> {
+    writer.write_all(id)?;
+    writer.write_all(&(body.len() as u32).to_le_bytes())?;
+    write_padded(writer, body)
+}
+
+/// Write `body` verbatim, then a zero pad byte if its length is odd. RIFF
+/// chunks are word-aligned on disk; `chunk_size_on_disk` already accounts for
+/// this pad byte in its size math, so every chunk body (including `data`)
+/// must actually emit one to match.
+fn write_padded(writer: &mut impl Write, body: &[u8]) -> io::Result<()> {
+    writer.write_all(body)?;
+    if body.len() % 2 == 1 {
+        writer.write_all(&[0u8])?;
+    }
     Ok(())
 }
 
+/// Total on-disk size of a RIFF chunk (id + size + body + alignment padding)
+fn chunk_size_on_disk(body_len: u32) -> u32 {
+    8 + body_len + (body_len % 2)
+}
+
 /// Utility function to convert minutes to Duration
 pub fn minutes_to_duration(minutes: u64) -> Duration {
     Duration::from_secs(minutes * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cleanup_streaming_attempt_removes_every_listed_file() {
+        let dir = std::env::temp_dir().join(format!("wav_splitter_test_{}_cleanup", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let files: Vec<PathBuf> = (0..3)
+            .map(|i| {
+                let path = dir.join(format!("chunk_{}.wav", i));
+                fs::write(&path, b"partial").unwrap();
+                path
+            })
+            .collect();
+
+        cleanup_streaming_attempt(&files);
+
+        for path in &files {
+            assert!(!path.exists());
+        }
+        fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn cleanup_streaming_attempt_tolerates_already_missing_files() {
+        let path = std::env::temp_dir().join(format!("wav_splitter_test_{}_missing.wav", std::process::id()));
+        assert!(!path.exists());
+        cleanup_streaming_attempt(&[path]); // must not panic or return an error
+    }
 }
\ No newline at end of file
@@ -1,12 +1,15 @@
 use std::fs;
 use std::io;
 use std::path::PathBuf;
-use wav_splitter::{split_wav, SplitOptions, minutes_to_duration};
+use wav_splitter::{
+    split_wav, InterpolationMode, SplitOptions, SplitMode, SplitStrategy, minutes_to_duration,
+    DEFAULT_SILENCE_THRESHOLD_DB, DEFAULT_MIN_SILENCE_MS,
+};
 
 fn main() -> io::Result<()> {
     // Parse command line arguments
     let args: Vec<String> = std::env::args().collect();
-    
+
     let (input_file, chunk_minutes, output_prefix) = if args.len() >= 4 {
         let file = PathBuf::from(&args[1]);
         let minutes = args[2].parse::<u64>().unwrap_or(10);
@@ -21,37 +24,90 @@ fn main() -> io::Result<()> {
         println!("  Output prefix: audiofile_part");
         println!("  Output folder: audio_chunks");
         println!();
-        println!("To specify custom parameters, use: cargo run -- <input_file> <chunk_minutes> <output_prefix>");
-        
+        println!("To specify custom parameters, use: cargo run -- <input_file> <chunk_minutes> <output_prefix> [options]");
+        println!("Options:");
+        println!("  --cue <file.cue>            Split at track boundaries from a CUE sheet instead of by duration");
+        println!("  --silence                   Split near the duration marks, snapped to detected silence");
+        println!("  --sample-rate <hz>           Resample each chunk to this rate (e.g. 16000 for speech pipelines)");
+        println!("  --interpolation <mode>       Interpolation used when resampling: nearest|linear|cosine|cubic");
+
         (default_input, 10, "audiofile_part".to_string())
     };
-    
+
+    let (cue_path, use_silence, target_sample_rate, interpolation_mode) = parse_split_flags(&args);
+
     let chunk_duration = minutes_to_duration(chunk_minutes);
+    let split_mode = match cue_path {
+        Some(path) => SplitMode::Cue(path),
+        None if use_silence => SplitMode::Silence(chunk_duration),
+        None => SplitMode::Duration(chunk_duration),
+    };
+
     let folder_name = "audio_chunks";
     match fs::create_dir(folder_name) {
         Ok(_) => println!("Directory {} created", folder_name),
         Err(_) => println!("Directory {} already exists", folder_name),
     }
     let output_dir = PathBuf::from(folder_name);
-    
+
     // Create split options from parameters
     let options = SplitOptions {
         input_path: &input_file,
-        chunk_duration,
+        split_mode,
         output_dir: &output_dir,
         prefix: &output_prefix,
+        silence_threshold_db: DEFAULT_SILENCE_THRESHOLD_DB,
+        min_silence_ms: DEFAULT_MIN_SILENCE_MS,
+        target_sample_rate,
+        interpolation_mode,
     };
-    
+
     // Execute the split operation
     match split_wav(&options) {
         Ok(result) => {
             println!("WAV file split completed successfully!");
-            println!("Created {} chunks with total duration of {:.2} minutes", 
+            println!("Created {} chunks with total duration of {:.2} minutes",
                      result.chunk_count,
                      result.total_duration.as_secs_f64() / 60.0);
+            println!("Strategy used: {}", match result.strategy {
+                SplitStrategy::Streaming => "single-pass streaming",
+                SplitStrategy::Buffered => "buffered two-pass",
+            });
         },
         Err(e) => eprintln!("Error: {}", e),
     }
-    
+
     Ok(())
 }
+
+/// Parse the optional `--cue`/`--silence`/`--sample-rate`/`--interpolation`
+/// flags that may follow the three positional arguments
+fn parse_split_flags(args: &[String]) -> (Option<PathBuf>, bool, Option<u32>, InterpolationMode) {
+    let mut cue_path = None;
+    let mut use_silence = false;
+    let mut target_sample_rate = None;
+    let mut interpolation_mode = InterpolationMode::default();
+
+    let mut rest = args.iter().skip(4);
+    while let Some(flag) = rest.next() {
+        match flag.as_str() {
+            "--cue" => cue_path = rest.next().map(PathBuf::from),
+            "--silence" => use_silence = true,
+            "--sample-rate" => target_sample_rate = rest.next().and_then(|value| value.parse().ok()),
+            "--interpolation" => interpolation_mode = match rest.next().map(String::as_str) {
+                Some("nearest") => InterpolationMode::Nearest,
+                Some("linear") => InterpolationMode::Linear,
+                Some("cosine") => InterpolationMode::Cosine,
+                Some("cubic") => InterpolationMode::Cubic,
+                Some(other) => {
+                    eprintln!("Unknown interpolation mode '{}', keeping default", other);
+                    interpolation_mode
+                }
+                None => interpolation_mode,
+            },
+            other => eprintln!("Ignoring unrecognized argument: {}", other),
+        }
+    }
+
+    (cue_path, use_silence, target_sample_rate, interpolation_mode)
+}
@@ -0,0 +1,195 @@
+//! Per-chunk sample-rate conversion, applied independently to each channel to
+//! avoid cross-channel smearing.
+
+/// Interpolation kernel used when resampling to `target_sample_rate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InterpolationMode {
+    /// Pick the nearest source sample
+    Nearest,
+    /// Linear blend between the two surrounding samples
+    #[default]
+    Linear,
+    /// Linear blend with a raised-cosine-weighted fraction, for a smoother transition
+    Cosine,
+    /// Catmull-Rom cubic interpolation across the four surrounding samples
+    Cubic,
+}
+
+/// Raw PCM sample encoding: fixed-point integer or IEEE-754 float. Needed
+/// because a 32-bit sample means two very different bit patterns depending
+/// on which one the source actually used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleFormat {
+    /// Signed (or 8-bit unsigned) fixed-point integer PCM
+    Int,
+    /// IEEE-754 float PCM (`WAVE_FORMAT_IEEEFLOAT`), normalized to [-1.0, 1.0]
+    Float,
+}
+
+/// Resample interleaved PCM bytes from `src_rate` to `dst_rate`, returning
+/// interleaved little-endian 16-bit PCM bytes at the new rate
+pub fn resample_pcm_bytes(
+    bytes: &[u8],
+    channels: u16,
+    bits_per_sample: u16,
+    sample_format: SampleFormat,
+    src_rate: u32,
+    dst_rate: u32,
+    mode: InterpolationMode,
+) -> Vec<u8> {
+    let per_channel = deinterleave_to_i16(bytes, channels as usize, bits_per_sample, sample_format);
+
+    if src_rate == dst_rate {
+        return interleave_i16(&per_channel);
+    }
+
+    let frame_count_src = per_channel.first().map(|channel| channel.len()).unwrap_or(0);
+    let frame_count_dst = ((frame_count_src as u64 * dst_rate as u64) / src_rate.max(1) as u64) as usize;
+
+    let resampled_channels: Vec<Vec<i16>> = per_channel
+        .iter()
+        .map(|channel| {
+            (0..frame_count_dst)
+                .map(|i| {
+                    let source_pos = i as f64 * src_rate as f64 / dst_rate as f64;
+                    sample_at(channel, source_pos, mode)
+                })
+                .collect()
+        })
+        .collect();
+
+    interleave_i16(&resampled_channels)
+}
+
+/// Interpolate the value of `channel` at fractional source position `p`
+fn sample_at(channel: &[i16], p: f64, mode: InterpolationMode) -> i16 {
+    let j = p.floor() as i64;
+    let t = p - j as f64;
+
+    let at = |index: i64| -> f64 {
+        let clamped = index.clamp(0, channel.len() as i64 - 1);
+        channel[clamped as usize] as f64
+    };
+
+    let value = match mode {
+        InterpolationMode::Nearest => at(p.round() as i64),
+        InterpolationMode::Linear => at(j) * (1.0 - t) + at(j + 1) * t,
+        InterpolationMode::Cosine => {
+            let t2 = (1.0 - (t * std::f64::consts::PI).cos()) / 2.0;
+            at(j) * (1.0 - t2) + at(j + 1) * t2
+        }
+        InterpolationMode::Cubic => catmull_rom(at(j - 1), at(j), at(j + 1), at(j + 2), t),
+    };
+
+    value.round().clamp(i16::MIN as f64, i16::MAX as f64) as i16
+}
+
+/// Standard Catmull-Rom cubic interpolation across four control points
+fn catmull_rom(p0: f64, p1: f64, p2: f64, p3: f64, t: f64) -> f64 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2.0 * p1
+        + (-p0 + p2) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (-p0 + 3.0 * p1 - 3.0 * p2 + p3) * t3)
+}
+
+/// Split interleaved PCM bytes into one `Vec<i16>` per channel, normalizing
+/// whatever bit depth/encoding the source used
+fn deinterleave_to_i16(bytes: &[u8], channels: usize, bits_per_sample: u16, sample_format: SampleFormat) -> Vec<Vec<i16>> {
+    let bytes_per_sample = (bits_per_sample / 8).max(1) as usize;
+    let frame_size = bytes_per_sample * channels.max(1);
+    let frame_count = if frame_size == 0 { 0 } else { bytes.len() / frame_size };
+
+    let mut per_channel = vec![Vec::with_capacity(frame_count); channels];
+    for frame in 0..frame_count {
+        let frame_start = frame * frame_size;
+        for (ch, channel_samples) in per_channel.iter_mut().enumerate() {
+            let sample_start = frame_start + ch * bytes_per_sample;
+            let sample_bytes = &bytes[sample_start..sample_start + bytes_per_sample];
+            channel_samples.push(decode_sample_i16(sample_bytes, bits_per_sample, sample_format));
+        }
+    }
+    per_channel
+}
+
+/// Decode one little-endian PCM sample of `bits_per_sample` width into `i16`
+fn decode_sample_i16(bytes: &[u8], bits_per_sample: u16, sample_format: SampleFormat) -> i16 {
+    match (bits_per_sample, sample_format) {
+        (8, SampleFormat::Int) => ((bytes[0] as i16) - 128) * 256, // 8-bit WAV PCM is unsigned
+        (16, SampleFormat::Int) => i16::from_le_bytes([bytes[0], bytes[1]]),
+        (24, SampleFormat::Int) => {
+            let raw = (bytes[2] as i32) << 16 | (bytes[1] as i32) << 8 | bytes[0] as i32;
+            let signed = (raw << 8) >> 8; // sign-extend 24 -> 32 bits
+            (signed >> 8) as i16
+        }
+        (32, SampleFormat::Int) => (i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) >> 16) as i16,
+        (32, SampleFormat::Float) => {
+            let sample = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16
+        }
+        (64, SampleFormat::Float) => {
+            let sample = f64::from_le_bytes([
+                bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+            ]);
+            (sample.clamp(-1.0, 1.0) * i16::MAX as f64).round() as i16
+        }
+        _ => 0,
+    }
+}
+
+/// Interleave one `Vec<i16>` per channel back into little-endian PCM bytes
+fn interleave_i16(per_channel: &[Vec<i16>]) -> Vec<u8> {
+    let channels = per_channel.len();
+    let frames = per_channel.first().map(|channel| channel.len()).unwrap_or(0);
+    let mut bytes = Vec::with_capacity(frames * channels * 2);
+    for frame in 0..frames {
+        for channel_samples in per_channel {
+            bytes.extend_from_slice(&channel_samples[frame].to_le_bytes());
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catmull_rom_passes_through_its_inner_control_points() {
+        assert_eq!(catmull_rom(0.0, 10.0, 20.0, 30.0, 0.0), 10.0);
+        assert_eq!(catmull_rom(0.0, 10.0, 20.0, 30.0, 1.0), 20.0);
+    }
+
+    #[test]
+    fn catmull_rom_is_constant_across_a_flat_signal() {
+        assert_eq!(catmull_rom(5.0, 5.0, 5.0, 5.0, 0.3), 5.0);
+    }
+
+    #[test]
+    fn sample_at_nearest_rounds_to_the_closest_index() {
+        let channel = [0i16, 10, 20, 30];
+        assert_eq!(sample_at(&channel, 1.4, InterpolationMode::Nearest), 10);
+        assert_eq!(sample_at(&channel, 1.6, InterpolationMode::Nearest), 20);
+    }
+
+    #[test]
+    fn sample_at_linear_interpolates_between_samples() {
+        let channel = [0i16, 10];
+        assert_eq!(sample_at(&channel, 0.5, InterpolationMode::Linear), 5);
+    }
+
+    #[test]
+    fn sample_at_clamps_past_the_channel_edges() {
+        let channel = [1i16, 2, 3];
+        assert_eq!(sample_at(&channel, -1.0, InterpolationMode::Linear), 1);
+        assert_eq!(sample_at(&channel, 5.0, InterpolationMode::Linear), 3);
+    }
+
+    #[test]
+    fn decode_sample_i16_reads_float_pcm_as_float_not_integer() {
+        let bytes = 0.5f32.to_le_bytes();
+        let sample = decode_sample_i16(&bytes, 32, SampleFormat::Float);
+        assert_eq!(sample, (0.5 * i16::MAX as f32).round() as i16);
+    }
+}
@@ -0,0 +1,147 @@
+//! RIFF chunk walker for WAV files.
+//!
+//! Rather than assuming a fixed 44-byte PCM header, this scans the input's
+//! actual chunk layout so `fmt `/`fact`/`LIST`/`INFO`/etc. survive a split
+//! byte-for-byte instead of being synthesized or silently dropped.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Chunk payloads preserved verbatim from the source WAV, to be re-emitted in
+/// every output file
+pub struct WavChunks {
+    /// Verbatim body of the `fmt ` chunk (no ID/size prefix)
+    pub fmt_chunk: Vec<u8>,
+    /// Verbatim body of the `fact` chunk, if present
+    pub fact_chunk: Option<Vec<u8>>,
+    /// Other chunks to propagate as-is, e.g. `LIST`/`INFO`/`cue `, as `(id, body)` pairs
+    pub metadata_chunks: Vec<([u8; 4], Vec<u8>)>,
+}
+
+/// Walk the RIFF chunks of a WAV file, preserving `fmt `/`fact`/metadata bodies
+///
+/// The `data` chunk itself is skipped here; its samples are read separately
+/// via the packet/decoder path.
+pub fn parse_riff_chunks(path: &Path) -> io::Result<WavChunks> {
+    let mut file = File::open(path)?;
+
+    let mut riff_tag = [0u8; 4];
+    file.read_exact(&mut riff_tag)?;
+    if &riff_tag != b"RIFF" {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not a RIFF file"));
+    }
+    file.seek(SeekFrom::Current(4))?; // overall RIFF size, recomputed per output file
+
+    let mut wave_tag = [0u8; 4];
+    file.read_exact(&mut wave_tag)?;
+    if &wave_tag != b"WAVE" {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not a WAVE file"));
+    }
+
+    let mut fmt_chunk = None;
+    let mut fact_chunk = None;
+    let mut metadata_chunks = Vec::new();
+
+    loop {
+        let mut id = [0u8; 4];
+        if file.read_exact(&mut id).is_err() {
+            break; // reached EOF cleanly between chunks
+        }
+
+        let mut size_buf = [0u8; 4];
+        file.read_exact(&mut size_buf)?;
+        let size = u32::from_le_bytes(size_buf);
+
+        match &id {
+            b"fmt " => fmt_chunk = Some(read_chunk_body(&mut file, size)?),
+            b"fact" => fact_chunk = Some(read_chunk_body(&mut file, size)?),
+            b"data" => {
+                // Samples are read via the packet/decoder path; just skip over them here.
+                file.seek(SeekFrom::Current(size as i64))?;
+            }
+            _ => metadata_chunks.push((id, read_chunk_body(&mut file, size)?)),
+        }
+
+        // RIFF chunks are word-aligned: skip the pad byte on an odd-sized chunk
+        if size % 2 == 1 {
+            file.seek(SeekFrom::Current(1))?;
+        }
+    }
+
+    let fmt_chunk = fmt_chunk.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing fmt chunk"))?;
+
+    Ok(WavChunks { fmt_chunk, fact_chunk, metadata_chunks })
+}
+
+/// Read a chunk's body, first checking `size` against the bytes actually left
+/// in the file so a truncated or corrupt chunk size can't trigger a huge
+/// allocation instead of a clean parse error
+fn read_chunk_body(file: &mut File, size: u32) -> io::Result<Vec<u8>> {
+    let remaining = file.metadata()?.len().saturating_sub(file.stream_position()?);
+    if size as u64 > remaining {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("chunk claims {} bytes but only {} remain in the file", size, remaining),
+        ));
+    }
+
+    let mut body = vec![0u8; size as usize];
+    file.read_exact(&mut body)?;
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write as _;
+
+    /// Write a minimal WAV with an odd-length `LIST` metadata chunk (padded
+    /// to a word boundary, as real RIFF files are) and check the chunk after
+    /// it is still found at the right offset
+    #[test]
+    fn parses_odd_length_chunk_and_recovers_the_one_after_it() {
+        let path = std::env::temp_dir().join(format!("wav_splitter_test_{}_oddchunk.wav", std::process::id()));
+        let mut file = File::create(&path).unwrap();
+
+        let fmt_body: [u8; 16] = [1, 0, 2, 0, 0x44, 0xac, 0, 0, 0x10, 0xb1, 2, 0, 4, 0, 16, 0];
+        let list_body = b"odd"; // 3 bytes: odd length, needs a pad byte on disk
+        let cue_body = [0xAAu8; 4]; // even length: no padding needed
+
+        let data_size = 0u32; // empty data chunk; its samples are not under test here
+        let riff_size = 4
+            + (8 + fmt_body.len() as u32)
+            + (8 + list_body.len() as u32 + 1) // + pad byte
+            + (8 + data_size)
+            + (8 + cue_body.len() as u32);
+
+        file.write_all(b"RIFF").unwrap();
+        file.write_all(&riff_size.to_le_bytes()).unwrap();
+        file.write_all(b"WAVE").unwrap();
+
+        file.write_all(b"fmt ").unwrap();
+        file.write_all(&(fmt_body.len() as u32).to_le_bytes()).unwrap();
+        file.write_all(&fmt_body).unwrap();
+
+        file.write_all(b"LIST").unwrap();
+        file.write_all(&(list_body.len() as u32).to_le_bytes()).unwrap();
+        file.write_all(list_body).unwrap();
+        file.write_all(&[0u8]).unwrap(); // pad byte
+
+        file.write_all(b"data").unwrap();
+        file.write_all(&data_size.to_le_bytes()).unwrap();
+
+        file.write_all(b"cue ").unwrap();
+        file.write_all(&(cue_body.len() as u32).to_le_bytes()).unwrap();
+        file.write_all(&cue_body).unwrap();
+        drop(file);
+
+        let chunks = parse_riff_chunks(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(chunks.fmt_chunk, fmt_body);
+        assert_eq!(chunks.metadata_chunks.len(), 2);
+        assert_eq!(chunks.metadata_chunks[0], (*b"LIST", list_body.to_vec()));
+        assert_eq!(chunks.metadata_chunks[1], (*b"cue ", cue_body.to_vec()));
+    }
+}
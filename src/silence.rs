@@ -0,0 +1,200 @@
+//! Content-aware silence detection used by `SplitMode::Silence` to place cut
+//! points between tracks rather than at exact duration marks.
+
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use symphonia::core::audio::{AudioBufferRef, Signal};
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::conv::IntoSample;
+use symphonia::core::errors::Error as SymphoniaError;
+
+use crate::open_format_reader;
+
+/// Width of the sliding RMS window used to look for silence
+const WINDOW_MS: u64 = 20;
+
+/// A candidate cut point: the lowest-energy sample found inside a contiguous
+/// run of windows that qualified as silent
+#[derive(Debug, Clone, Copy)]
+pub struct SilencePoint {
+    /// Position of the lowest-energy sample within the silent run
+    pub time: Duration,
+    /// RMS level at that position, in dBFS
+    pub energy_db: f64,
+}
+
+/// Decode `input_path` end-to-end and return one `SilencePoint` per contiguous
+/// run of windows at or below `threshold_db` that lasts at least `min_silence_ms`
+pub fn find_silence_points(
+    input_path: &Path,
+    threshold_db: f64,
+    min_silence_ms: u64,
+) -> io::Result<Vec<SilencePoint>> {
+    let mut format = open_format_reader(input_path)?;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No decodable track found"))?
+        .clone();
+
+    let track_id = track.id;
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No sample rate found"))? as u64;
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Error creating decoder: {}", e)))?;
+
+    let window_frames = ((sample_rate * WINDOW_MS) / 1000).max(1) as usize;
+    let min_silent_windows = (min_silence_ms as f64 / WINDOW_MS as f64).ceil() as usize;
+
+    let mut points = Vec::new();
+    let mut frame_pos: u64 = 0;
+
+    // State for the silent run currently being accumulated, if any
+    let mut run_len = 0usize;
+    let mut run_best_frame: u64 = 0;
+    let mut run_best_db = f64::INFINITY;
+
+    // Decoded samples not yet long enough to fill a window, one Vec per channel
+    let mut pending: Vec<Vec<f32>> = Vec::new();
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(_)) => break, // end of stream
+            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!("Error reading packet: {}", e))),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(SymphoniaError::DecodeError(_)) => continue, // skip the bad packet and keep going
+            Err(e) => return Err(io::Error::new(io::ErrorKind::Other, format!("Error decoding packet: {}", e))),
+        };
+
+        append_planar_samples(&decoded, &mut pending);
+
+        while pending.iter().all(|channel| channel.len() >= window_frames) {
+            let mut sum_sq = 0.0f64;
+            let mut count = 0usize;
+            for channel in &pending {
+                for &sample in &channel[..window_frames] {
+                    sum_sq += (sample as f64) * (sample as f64);
+                    count += 1;
+                }
+            }
+            let rms = (sum_sq / count.max(1) as f64).sqrt();
+            let db = 20.0 * rms.max(1e-12).log10();
+
+            if db <= threshold_db {
+                // Track the single quietest frame (RMS across channels) of the whole run
+                for i in 0..window_frames {
+                    let frame_sq: f64 = pending.iter().map(|channel| (channel[i] as f64).powi(2)).sum();
+                    let frame_db = 20.0 * (frame_sq / pending.len().max(1) as f64).sqrt().max(1e-12).log10();
+                    if frame_db < run_best_db {
+                        run_best_db = frame_db;
+                        run_best_frame = frame_pos + i as u64;
+                    }
+                }
+                run_len += 1;
+            } else {
+                push_run_if_qualifying(&mut points, run_len, min_silent_windows, run_best_frame, run_best_db, sample_rate);
+                run_len = 0;
+                run_best_db = f64::INFINITY;
+            }
+
+            for channel in pending.iter_mut() {
+                channel.drain(..window_frames);
+            }
+            frame_pos += window_frames as u64;
+        }
+    }
+
+    push_run_if_qualifying(&mut points, run_len, min_silent_windows, run_best_frame, run_best_db, sample_rate);
+
+    Ok(points)
+}
+
+/// Record the current silent run as a `SilencePoint` if it met the minimum duration
+fn push_run_if_qualifying(
+    points: &mut Vec<SilencePoint>,
+    run_len: usize,
+    min_silent_windows: usize,
+    run_best_frame: u64,
+    run_best_db: f64,
+    sample_rate: u64,
+) {
+    if run_len >= min_silent_windows {
+        points.push(SilencePoint {
+            time: Duration::from_secs_f64(run_best_frame as f64 / sample_rate as f64),
+            energy_db: run_best_db,
+        });
+    }
+}
+
+/// Append a decoded buffer's samples to `pending`, one `Vec<f32>` per channel,
+/// converting whichever sample format Symphonia decoded into normalized `f32`
+fn append_planar_samples(decoded: &AudioBufferRef, pending: &mut Vec<Vec<f32>>) {
+    macro_rules! append {
+        ($buf:expr) => {{
+            let channels = $buf.spec().channels.count();
+            if pending.is_empty() {
+                pending.resize_with(channels, Vec::new);
+            }
+            for ch in 0..channels {
+                pending[ch].extend($buf.chan(ch).iter().map(|&sample| IntoSample::<f32>::into_sample(sample)));
+            }
+        }};
+    }
+
+    match decoded {
+        AudioBufferRef::U8(buf) => append!(buf),
+        AudioBufferRef::U16(buf) => append!(buf),
+        AudioBufferRef::U24(buf) => append!(buf),
+        AudioBufferRef::U32(buf) => append!(buf),
+        AudioBufferRef::S8(buf) => append!(buf),
+        AudioBufferRef::S16(buf) => append!(buf),
+        AudioBufferRef::S24(buf) => append!(buf),
+        AudioBufferRef::S32(buf) => append!(buf),
+        AudioBufferRef::F32(buf) => append!(buf),
+        AudioBufferRef::F64(buf) => append!(buf),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_run_if_qualifying_records_a_run_meeting_the_minimum() {
+        let mut points = Vec::new();
+        push_run_if_qualifying(&mut points, 5, 3, 100, -50.0, 1000);
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].time, Duration::from_millis(100));
+        assert_eq!(points[0].energy_db, -50.0);
+    }
+
+    #[test]
+    fn push_run_if_qualifying_drops_a_run_shorter_than_the_minimum() {
+        let mut points = Vec::new();
+        push_run_if_qualifying(&mut points, 2, 3, 100, -50.0, 1000);
+        assert!(points.is_empty());
+    }
+
+    #[test]
+    fn push_run_if_qualifying_accepts_a_run_at_exactly_the_minimum() {
+        let mut points = Vec::new();
+        push_run_if_qualifying(&mut points, 3, 3, 0, -60.0, 1000);
+        assert_eq!(points.len(), 1);
+    }
+}